@@ -0,0 +1,73 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// One document's outgoing links, as passed in to `build_backlinks`.
+#[derive(Deserialize)]
+struct Document {
+    id: String,
+    #[serde(default)]
+    outgoing_links: Vec<String>,
+}
+
+/// Builds an inverted index over the link graph: for every document id seen
+/// (either as a source or as a target), the list of document ids that link
+/// to it.
+pub fn build(documents_json: &str) -> Result<Value, serde_json::Error> {
+    let documents: Vec<Document> = serde_json::from_str(documents_json)?;
+
+    let mut backlinks: HashMap<String, Vec<String>> = HashMap::new();
+    for document in &documents {
+        backlinks.entry(document.id.clone()).or_default();
+    }
+    for document in &documents {
+        // Dedupe a document's own outgoing links first, so linking to the
+        // same target twice doesn't produce duplicate backlink entries.
+        let targets: HashSet<&String> = document.outgoing_links.iter().collect();
+        for target in targets {
+            backlinks
+                .entry(target.clone())
+                .or_default()
+                .push(document.id.clone());
+        }
+    }
+
+    serde_json::to_value(backlinks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupes_repeated_targets_from_the_same_source() {
+        let input = r#"[
+            {"id": "a", "outgoing_links": ["b", "b"]},
+            {"id": "b", "outgoing_links": []}
+        ]"#;
+
+        let result = build(input).unwrap();
+        let b_backlinks = result["b"].as_array().unwrap();
+        assert_eq!(b_backlinks, &[Value::String("a".to_string())]);
+    }
+
+    #[test]
+    fn keeps_one_entry_per_distinct_source() {
+        let input = r#"[
+            {"id": "a", "outgoing_links": ["c"]},
+            {"id": "b", "outgoing_links": ["c"]},
+            {"id": "c", "outgoing_links": []}
+        ]"#;
+
+        let result = build(input).unwrap();
+        let mut c_backlinks: Vec<String> = result["c"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect();
+        c_backlinks.sort();
+        assert_eq!(c_backlinks, vec!["a".to_string(), "b".to_string()]);
+    }
+}
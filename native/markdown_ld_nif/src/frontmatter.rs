@@ -0,0 +1,90 @@
+use serde_json::Value;
+
+/// Splits a leading `---`-delimited YAML frontmatter block off of `markdown`.
+///
+/// Returns the parsed frontmatter (or `None` if the document has none or the
+/// block fails to parse as YAML) and the remaining body with the block
+/// removed.
+pub fn split_frontmatter(markdown: &str) -> (Option<Value>, &str) {
+    let Some(rest) = markdown.strip_prefix("---") else {
+        return (None, markdown);
+    };
+    // Frontmatter must open on its own line.
+    let rest = match rest.strip_prefix('\r') {
+        Some(r) => r.strip_prefix('\n').unwrap_or(r),
+        None => match rest.strip_prefix('\n') {
+            Some(r) => r,
+            None => return (None, markdown),
+        },
+    };
+
+    let Some(end) = find_closing_fence(rest) else {
+        return (None, markdown);
+    };
+
+    let yaml = &rest[..end.start];
+    let body = &rest[end.end..];
+
+    match serde_yaml::from_str::<serde_yaml::Value>(yaml) {
+        // Only a mapping (`key: value` pairs) counts as frontmatter. A
+        // document that merely opens with a `---` thematic break followed by
+        // plain text and another `---` parses as a bare YAML scalar, and
+        // must be left alone as body content instead.
+        Ok(value @ serde_yaml::Value::Mapping(_)) => match serde_json::to_value(value) {
+            Ok(json) => (Some(json), body),
+            Err(_) => (None, markdown),
+        },
+        Ok(_) => (None, markdown),
+        Err(_) => (None, markdown),
+    }
+}
+
+struct Fence {
+    start: usize,
+    end: usize,
+}
+
+/// Finds the `---` or `...` line that closes the frontmatter block, returning
+/// the byte range of the line (including its trailing newline) in `rest`.
+fn find_closing_fence(rest: &str) -> Option<Fence> {
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            return Some(Fence {
+                start: offset,
+                end: offset + line.len(),
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mapping_frontmatter() {
+        let (frontmatter, body) = split_frontmatter("---\ntitle: Hello\n---\nbody text");
+        assert_eq!(frontmatter.unwrap()["title"], "Hello");
+        assert_eq!(body, "body text");
+    }
+
+    #[test]
+    fn rejects_non_mapping_frontmatter() {
+        let markdown = "---\nSome text\n---\nmore";
+        let (frontmatter, body) = split_frontmatter(markdown);
+        assert!(frontmatter.is_none());
+        assert_eq!(body, markdown);
+    }
+
+    #[test]
+    fn no_frontmatter_when_document_does_not_open_with_a_fence() {
+        let markdown = "# Heading\n\nbody";
+        let (frontmatter, body) = split_frontmatter(markdown);
+        assert!(frontmatter.is_none());
+        assert_eq!(body, markdown);
+    }
+}
@@ -0,0 +1,25 @@
+use serde_json::Value;
+
+/// Serializes `value` to a JSON string via simd_json's writer.
+///
+/// simd_json's real speed advantage is on the parse side, not here: `value`
+/// is already a built `serde_json::Value`, so this call gets simd_json's
+/// vectorized string-escaping and number-formatting but none of the
+/// SIMD-accelerated parsing that's the bulk of its reputation. The wire
+/// format matches `serde_json::to_string`, so the Elixir side's decoder
+/// needs no changes either way.
+pub fn to_string(value: &Value) -> Result<String, simd_json::Error> {
+    simd_json::to_string(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn wire_format_matches_serde_json() {
+        let value = json!({"a": 1, "b": [true, null, "text"]});
+        assert_eq!(to_string(&value).unwrap(), serde_json::to_string(&value).unwrap());
+    }
+}
@@ -1,5 +1,4 @@
-use rustler::{Binary, Env, NifResult, Term, Encoder};
-use serde_json;
+use rustler::{Binary, Env, NifResult, ResourceArc, Term, Encoder};
 
 mod atoms {
     rustler::atoms! {
@@ -10,60 +9,120 @@ mod atoms {
     }
 }
 
-#[rustler::nif]
+mod backlinks;
+mod frontmatter;
+mod json;
+mod parser;
+mod streaming;
+mod toc;
+mod validate;
+mod wikilinks;
+
+#[rustler::nif(schedule = "DirtyCpu")]
 fn parse_markdown<'a>(env: Env<'a>, markdown_binary: Binary) -> NifResult<Term<'a>> {
     let markdown_str = match std::str::from_utf8(markdown_binary.as_slice()) {
         Ok(s) => s,
         Err(_) => return Ok((atoms::error(), atoms::invalid_markdown()).encode(env)),
     };
 
-    // Simple word count for now
-    let word_count = markdown_str.split_whitespace().count();
-    let reading_time = (word_count as f64 / 200.0).ceil() as usize;
-
-    let result = serde_json::json!({
-        "links": [],
-        "headings": [],
-        "code_blocks": [],
-        "tasks": [],
-        "word_count": word_count,
-        "reading_time_minutes": reading_time,
-        "metadata": {},
-        "table_of_contents": [],
-        "backlinks": [],
-        "frontmatter": null
-    });
-
-    match result.to_string() {
-        json_str => Ok((atoms::ok(), json_str).encode(env)),
+    let result = parser::analyze(markdown_str).into_json();
+
+    match json::to_string(&result) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
     }
 }
 
 #[rustler::nif]
 fn extract_links<'a>(env: Env<'a>, _markdown_binary: Binary) -> NifResult<Term<'a>> {
     let result = serde_json::json!([]);
-    match result.to_string() {
-        json_str => Ok((atoms::ok(), json_str).encode(env)),
+    match json::to_string(&result) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
     }
 }
 
 #[rustler::nif]
 fn extract_headings<'a>(env: Env<'a>, _markdown_binary: Binary) -> NifResult<Term<'a>> {
     let result = serde_json::json!([]);
-    match result.to_string() {
-        json_str => Ok((atoms::ok(), json_str).encode(env)),
+    match json::to_string(&result) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
     }
 }
 
 #[rustler::nif]
-fn validate_links<'a>(env: Env<'a>, _links_json: Binary) -> NifResult<Term<'a>> {
-    let result = serde_json::json!([]);
-    match result.to_string() {
-        json_str => Ok((atoms::ok(), json_str).encode(env)),
+fn validate_links<'a>(env: Env<'a>, links_json: Binary) -> NifResult<Term<'a>> {
+    let links_str = match std::str::from_utf8(links_json.as_slice()) {
+        Ok(s) => s,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_markdown()).encode(env)),
+    };
+
+    let diagnostics = match validate::validate(links_str) {
+        Ok(diagnostics) => diagnostics,
+        Err(_) => return Ok((atoms::error(), atoms::processing_error()).encode(env)),
+    };
+
+    match json::to_string(&diagnostics) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn build_backlinks<'a>(env: Env<'a>, documents_json: Binary) -> NifResult<Term<'a>> {
+    let documents_str = match std::str::from_utf8(documents_json.as_slice()) {
+        Ok(s) => s,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_markdown()).encode(env)),
+    };
+
+    let backlinks = match backlinks::build(documents_str) {
+        Ok(backlinks) => backlinks,
+        Err(_) => return Ok((atoms::error(), atoms::processing_error()).encode(env)),
+    };
+
+    match json::to_string(&backlinks) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
+    }
+}
+
+#[rustler::nif]
+fn start_markdown_parse<'a>(env: Env<'a>) -> NifResult<Term<'a>> {
+    Ok(streaming::start().encode(env))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn parse_markdown_chunk<'a>(
+    env: Env<'a>,
+    handle: ResourceArc<streaming::MarkdownParserHandle>,
+    chunk_binary: Binary,
+) -> NifResult<Term<'a>> {
+    // Buffered as raw bytes, not validated here: a multi-byte codepoint can
+    // land split across two chunks, and UTF-8 validity is only checked once
+    // against the whole accumulated document in `finish_markdown`.
+    streaming::push_chunk(&handle, chunk_binary.as_slice());
+    Ok(atoms::ok().encode(env))
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn finish_markdown<'a>(
+    env: Env<'a>,
+    handle: ResourceArc<streaming::MarkdownParserHandle>,
+) -> NifResult<Term<'a>> {
+    let result = match streaming::finish(&handle) {
+        Ok(result) => result,
+        Err(_) => return Ok((atoms::error(), atoms::invalid_markdown()).encode(env)),
+    };
+
+    match json::to_string(&result) {
+        Ok(json_str) => Ok((atoms::ok(), json_str).encode(env)),
+        Err(_) => Ok((atoms::error(), atoms::processing_error()).encode(env)),
     }
 }
 
-rustler::init!(
-    "Elixir.Kyozo.Storage.MarkdownLD",
-    [parse_markdown, extract_links, extract_headings, validate_links]
-);
\ No newline at end of file
+// Each #[rustler::nif] above self-registers a `Nif` descriptor, and
+// `MarkdownParserHandle`'s `#[rustler::resource_impl]` self-registers its
+// resource type the same way, so `init!` needs neither an explicit function
+// list nor an `on_load` resource callback.
+rustler::init!("Elixir.Kyozo.Storage.MarkdownLD");
\ No newline at end of file
@@ -0,0 +1,267 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+use serde_json::{json, Value};
+
+use crate::frontmatter::split_frontmatter;
+use crate::toc::{self, HeadingRef};
+use crate::wikilinks;
+
+/// Full analysis of a Markdown document, as returned by `parse_markdown`.
+pub struct Analysis {
+    pub links: Vec<Value>,
+    pub headings: Vec<Value>,
+    pub code_blocks: Vec<Value>,
+    pub tasks: Vec<Value>,
+    pub word_count: usize,
+    pub reading_time_minutes: usize,
+    pub frontmatter: Option<Value>,
+    pub table_of_contents: Vec<Value>,
+}
+
+/// Per-list-item accumulator, pushed on `Tag::Item` and popped on its
+/// matching `End`. Kept as a stack (rather than one shared buffer) so that a
+/// nested item doesn't clobber or get folded into its parent's text.
+#[derive(Default)]
+struct ItemFrame {
+    text: String,
+    task_checked: Option<bool>,
+}
+
+pub fn analyze(markdown: &str) -> Analysis {
+    let (frontmatter, body) = split_frontmatter(markdown);
+
+    let word_count = body.split_whitespace().count();
+    let reading_time_minutes = ((word_count as f64 / 200.0).ceil() as usize).max(1);
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut links = Vec::new();
+    let mut heading_refs: Vec<HeadingRef> = Vec::new();
+    let mut code_blocks = Vec::new();
+    let mut tasks = Vec::new();
+
+    // Text of the current heading or top-level paragraph outside any list
+    // item (list items, and anything nested in them, accumulate into their
+    // own `ItemFrame` instead). Wiki-links are scanned against this whole buffer at the end of each
+    // scope rather than per `Text` event, because pulldown-cmark can split a
+    // single `[[Target]]` across several `Text` events (it tries ordinary
+    // link parsing on the brackets first and backs out character by
+    // character).
+    let mut text_buf = String::new();
+    let mut in_heading: Option<HeadingLevel> = None;
+    let mut in_link: Option<(String, String)> = None; // (dest_url, title)
+    let mut link_text_buf: Option<String> = None;
+    let mut in_code_block: Option<Option<String>> = None; // fence info string
+    let mut code_buf = String::new();
+    // List items nest, so each open `Tag::Item` gets its own frame with its
+    // own text buffer and task-checked state; otherwise an inner item would
+    // clobber (or get double-scanned alongside) its parent's accumulated
+    // text. `item_stack` holds the currently-open ancestor items, innermost
+    // last.
+    let mut item_stack: Vec<ItemFrame> = Vec::new();
+    let mut in_paragraph = false;
+
+    for event in Parser::new_ext(body, options) {
+        match event {
+            Event::Start(Tag::Heading(level, ..)) => {
+                in_heading = Some(level);
+                text_buf.clear();
+            }
+            Event::End(Tag::Heading(level, ..)) => {
+                links.extend(wikilinks::scan(&text_buf));
+                heading_refs.push(HeadingRef {
+                    level: heading_level_number(level),
+                    text: text_buf.trim().to_string(),
+                });
+                in_heading = None;
+            }
+            Event::Start(Tag::Paragraph) if in_heading.is_none() && item_stack.is_empty() => {
+                in_paragraph = true;
+                text_buf.clear();
+            }
+            Event::End(Tag::Paragraph) if in_paragraph => {
+                links.extend(wikilinks::scan(&text_buf));
+                in_paragraph = false;
+            }
+            Event::Start(Tag::Link(_link_type, dest_url, title)) => {
+                in_link = Some((dest_url.to_string(), title.to_string()));
+                link_text_buf = Some(String::new());
+            }
+            Event::End(Tag::Link(..)) => {
+                if let (Some((url, title)), Some(inner)) = (in_link.take(), link_text_buf.take()) {
+                    links.push(json!({
+                        "kind": "link",
+                        "text": inner.trim().to_string(),
+                        "url": url,
+                        "title": if title.is_empty() { Value::Null } else { Value::String(title) },
+                    }));
+                    // Fold the link's own text back into the enclosing
+                    // heading/item/paragraph buffer so that context isn't
+                    // lost.
+                    if let Some(frame) = item_stack.last_mut() {
+                        frame.text.push_str(&inner);
+                    } else if in_heading.is_some() || in_paragraph {
+                        text_buf.push_str(&inner);
+                    }
+                }
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                in_code_block = Some(match kind {
+                    CodeBlockKind::Fenced(info) if !info.is_empty() => Some(info.to_string()),
+                    _ => None,
+                });
+                code_buf.clear();
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(language) = in_code_block.take() {
+                    code_blocks.push(json!({
+                        "language": language,
+                        "code": code_buf.trim_end_matches('\n').to_string(),
+                    }));
+                }
+            }
+            Event::Start(Tag::Item) => {
+                item_stack.push(ItemFrame::default());
+            }
+            Event::End(Tag::Item) => {
+                if let Some(frame) = item_stack.pop() {
+                    links.extend(wikilinks::scan(&frame.text));
+                    if let Some(checked) = frame.task_checked {
+                        tasks.push(json!({
+                            "text": frame.text.trim().to_string(),
+                            "checked": checked,
+                        }));
+                    }
+                }
+            }
+            Event::TaskListMarker(checked) => {
+                if let Some(frame) = item_stack.last_mut() {
+                    frame.task_checked = Some(checked);
+                }
+            }
+            Event::Text(text) => {
+                if in_code_block.is_some() {
+                    code_buf.push_str(&text);
+                } else if let Some(buf) = link_text_buf.as_mut() {
+                    buf.push_str(&text);
+                } else if let Some(frame) = item_stack.last_mut() {
+                    frame.text.push_str(&text);
+                } else if in_heading.is_some() || in_paragraph {
+                    text_buf.push_str(&text);
+                }
+            }
+            Event::Code(text) => {
+                if let Some(buf) = link_text_buf.as_mut() {
+                    buf.push_str(&text);
+                } else if let Some(frame) = item_stack.last_mut() {
+                    frame.text.push_str(&text);
+                } else if in_heading.is_some() || in_paragraph {
+                    text_buf.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (table_of_contents, slugs) = toc::build(&heading_refs);
+    let headings = heading_refs
+        .into_iter()
+        .zip(slugs)
+        .map(|(heading, slug)| {
+            json!({
+                "level": heading.level,
+                "text": heading.text,
+                "slug": slug,
+            })
+        })
+        .collect();
+
+    Analysis {
+        links,
+        headings,
+        code_blocks,
+        tasks,
+        word_count,
+        reading_time_minutes,
+        frontmatter,
+        table_of_contents,
+    }
+}
+
+fn heading_level_number(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+impl Analysis {
+    pub fn into_json(self) -> Value {
+        json!({
+            "links": self.links,
+            "headings": self.headings,
+            "code_blocks": self.code_blocks,
+            "tasks": self.tasks,
+            "word_count": self.word_count,
+            "reading_time_minutes": self.reading_time_minutes,
+            "metadata": {},
+            "table_of_contents": self.table_of_contents,
+            "backlinks": [],
+            "frontmatter": self.frontmatter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wikilink_targets(analysis: &Analysis) -> Vec<String> {
+        analysis
+            .links
+            .iter()
+            .filter(|link| link["kind"] == "wikilink")
+            .map(|link| link["target"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn nested_list_items_each_scan_their_own_text() {
+        let analysis = analyze("- outer [[A]] text\n  - inner [[B]] text\n");
+        let mut targets = wikilink_targets(&analysis);
+        targets.sort();
+        assert_eq!(targets, vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn heading_and_paragraph_wikilinks_are_found() {
+        let analysis = analyze("# Heading [[A]]\n\nParagraph with [[B|alias]] text.\n");
+        let mut targets = wikilink_targets(&analysis);
+        targets.sort();
+        assert_eq!(targets, vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn task_list_items_are_reported_with_checked_state() {
+        let analysis = analyze("- [x] done\n- [ ] todo\n");
+        assert_eq!(analysis.tasks.len(), 2);
+        assert_eq!(analysis.tasks[0]["checked"], true);
+        assert_eq!(analysis.tasks[0]["text"], "done");
+        assert_eq!(analysis.tasks[1]["checked"], false);
+        assert_eq!(analysis.tasks[1]["text"], "todo");
+    }
+
+    #[test]
+    fn headings_are_collected_with_slugs() {
+        let analysis = analyze("# Title\n\n## Title\n");
+        assert_eq!(analysis.headings[0]["slug"], "title");
+        assert_eq!(analysis.headings[1]["slug"], "title-1");
+    }
+}
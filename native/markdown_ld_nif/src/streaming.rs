@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+
+use rustler::ResourceArc;
+use serde_json::Value;
+
+use crate::parser;
+
+/// Resource-backed handle for incremental Markdown parsing: callers push
+/// chunks of a large document as they arrive (e.g. streamed from storage)
+/// and request the accumulated analysis once the document is complete.
+///
+/// Chunks are buffered as raw bytes rather than decoded as UTF-8 as each one
+/// arrives, because a multi-byte codepoint can land split across two chunk
+/// boundaries (storage reads are sized independently of character
+/// boundaries). The buffer is only decoded as a whole in `finish`.
+pub struct MarkdownParserHandle(Mutex<Vec<u8>>);
+
+impl MarkdownParserHandle {
+    fn new() -> Self {
+        MarkdownParserHandle(Mutex::new(Vec::new()))
+    }
+}
+
+// Self-registers via inventory, same mechanism as #[rustler::nif] — no
+// on_load wiring needed.
+#[rustler::resource_impl]
+impl rustler::Resource for MarkdownParserHandle {}
+
+pub fn start() -> ResourceArc<MarkdownParserHandle> {
+    ResourceArc::new(MarkdownParserHandle::new())
+}
+
+// Takes the handle itself (rather than a `ResourceArc<MarkdownParserHandle>`)
+// so the buffering logic can be exercised directly in tests, without going
+// through rustler's resource allocator.
+pub fn push_chunk(handle: &MarkdownParserHandle, chunk: &[u8]) {
+    handle.0.lock().unwrap().extend_from_slice(chunk);
+}
+
+/// Runs the full analysis over everything accumulated so far. The handle can
+/// still be fed more chunks afterwards; each call re-analyzes the whole
+/// document accumulated up to that point.
+///
+/// Fails only if the bytes accumulated so far aren't valid UTF-8 once taken
+/// together — a chunk split mid-codepoint no longer causes that, since
+/// decoding happens here rather than per `push_chunk` call.
+pub fn finish(handle: &MarkdownParserHandle) -> Result<Value, std::str::Utf8Error> {
+    let bytes = handle.0.lock().unwrap();
+    let markdown = std::str::from_utf8(&bytes)?;
+    Ok(parser::analyze(markdown).into_json())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reassembles_a_multi_byte_codepoint_split_across_chunks() {
+        let handle = MarkdownParserHandle::new();
+        let heading = "# héllo\n".as_bytes();
+        // Split the UTF-8 encoding of 'é' (0xC3 0xA9) across two chunks.
+        let split = heading.iter().position(|&b| b == 0xA9).unwrap();
+        push_chunk(&handle, &heading[..split]);
+        push_chunk(&handle, &heading[split..]);
+
+        let result = finish(&handle).unwrap();
+        assert_eq!(result["headings"][0]["text"], "héllo");
+    }
+}
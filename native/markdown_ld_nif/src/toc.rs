@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+/// One heading, as seen by the table-of-contents builder.
+pub struct HeadingRef {
+    pub level: u8,
+    pub text: String,
+}
+
+/// A single entry in the built table of contents.
+struct Entry {
+    text: String,
+    level: u8,
+    section: String,
+    slug: String,
+    children: Vec<Entry>,
+}
+
+impl Entry {
+    fn into_json(self) -> Value {
+        json!({
+            "text": self.text,
+            "level": self.level,
+            "section": self.section,
+            "slug": self.slug,
+            "children": self.children.into_iter().map(Entry::into_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Builds a nested table of contents from a flat heading list, following the
+/// same stack-based construction as rustdoc's `html::toc` module: a chain
+/// holds the path of currently-open ancestor headings, and each new heading
+/// pops the chain down to its parent (folding closed subtrees into their
+/// parent's children) before being pushed onto the chain itself.
+///
+/// Returns the TOC tree alongside the slug assigned to each input heading (in
+/// the same order as `headings`), so callers can stamp it back onto the
+/// `headings` array.
+pub fn build(headings: &[HeadingRef]) -> (Vec<Value>, Vec<String>) {
+    let mut roots: Vec<Entry> = Vec::new();
+    let mut chain: Vec<Entry> = Vec::new();
+    let mut slugger = Slugger::default();
+    let mut slugs = Vec::with_capacity(headings.len());
+
+    for heading in headings {
+        let slug = slugger.slugify(&heading.text);
+        slugs.push(slug.clone());
+
+        while chain
+            .last()
+            .map(|entry| entry.level >= heading.level)
+            .unwrap_or(false)
+        {
+            let closed = chain.pop().unwrap();
+            match chain.last_mut() {
+                Some(parent) => parent.children.push(closed),
+                None => roots.push(closed),
+            }
+        }
+
+        let sibling_index = match chain.last() {
+            Some(parent) => parent.children.len() + 1,
+            None => roots.len() + 1,
+        };
+        let section = match chain.last() {
+            Some(parent) => format!("{}.{}", parent.section, sibling_index),
+            None => sibling_index.to_string(),
+        };
+
+        chain.push(Entry {
+            text: heading.text.clone(),
+            level: heading.level,
+            section,
+            slug,
+            children: Vec::new(),
+        });
+    }
+
+    while let Some(closed) = chain.pop() {
+        match chain.last_mut() {
+            Some(parent) => parent.children.push(closed),
+            None => roots.push(closed),
+        }
+    }
+
+    let toc = roots.into_iter().map(Entry::into_json).collect();
+    (toc, slugs)
+}
+
+/// Generates URL-safe anchor slugs, disambiguating duplicates with a numeric
+/// suffix (`heading`, `heading-1`, `heading-2`, ...).
+#[derive(Default)]
+struct Slugger {
+    seen: HashMap<String, usize>,
+}
+
+impl Slugger {
+    fn slugify(&mut self, text: &str) -> String {
+        let base = slug_base(text);
+        let mut slug = base.clone();
+        // Suffixed slugs are recorded too, so a later heading whose own base
+        // is e.g. `heading-1` can't collide with one generated here.
+        while self.seen.contains_key(&slug) {
+            let count = self.seen.get_mut(&base).unwrap();
+            slug = format!("{base}-{count}");
+            *count += 1;
+        }
+        self.seen.insert(slug.clone(), 1);
+        slug
+    }
+}
+
+fn slug_base(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut last_was_dash = false;
+    for ch in text.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+            last_was_dash = false;
+        } else if !last_was_dash && !slug.is_empty() {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(level: u8, text: &str) -> HeadingRef {
+        HeadingRef {
+            level,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn nests_headings_under_their_parent() {
+        let headings = vec![heading(1, "Intro"), heading(2, "Background"), heading(1, "Usage")];
+        let (toc, slugs) = build(&headings);
+
+        assert_eq!(slugs, vec!["intro", "background", "usage"]);
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0]["text"], "Intro");
+        assert_eq!(toc[0]["children"][0]["text"], "Background");
+        assert_eq!(toc[1]["text"], "Usage");
+        assert_eq!(toc[1]["children"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn disambiguates_duplicate_slugs() {
+        let headings = vec![heading(1, "Overview"), heading(1, "Overview"), heading(1, "Overview")];
+        let (_, slugs) = build(&headings);
+
+        assert_eq!(slugs, vec!["overview", "overview-1", "overview-2"]);
+    }
+
+    #[test]
+    fn closes_deeper_subtrees_when_a_shallower_heading_arrives() {
+        let headings = vec![
+            heading(1, "A"),
+            heading(2, "A.1"),
+            heading(3, "A.1.a"),
+            heading(1, "B"),
+        ];
+        let (toc, _) = build(&headings);
+
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0]["children"][0]["children"][0]["text"], "A.1.a");
+        assert_eq!(toc[1]["text"], "B");
+    }
+
+    #[test]
+    fn empty_heading_text_gets_a_fallback_slug() {
+        let headings = vec![heading(1, "...")];
+        let (_, slugs) = build(&headings);
+
+        assert_eq!(slugs, vec!["section"]);
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// One entry of the `links` array produced by `parse_markdown`, as consumed
+/// by `validate_links`.
+#[derive(Deserialize)]
+struct LinkRecord {
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    fragment: Option<String>,
+}
+
+/// The set of anchor/document targets a link is allowed to resolve against,
+/// supplied by the Elixir side since the NIF only sees one document at a
+/// time.
+#[derive(Deserialize, Default)]
+pub struct KnownTargets {
+    #[serde(default)]
+    pub heading_slugs: HashSet<String>,
+    #[serde(default)]
+    pub document_ids: HashSet<String>,
+}
+
+#[derive(Clone, Copy)]
+enum Category {
+    External,
+    Relative,
+    Anchor,
+    Wikilink,
+}
+
+impl Category {
+    fn as_str(self) -> &'static str {
+        match self {
+            Category::External => "external",
+            Category::Relative => "relative",
+            Category::Anchor => "anchor",
+            Category::Wikilink => "wikilink",
+        }
+    }
+}
+
+/// Payload accepted by `validate_links`. Accepts either a bare array of link
+/// records (`known_targets` defaults to empty, i.e. every anchor/wikilink
+/// resolves) or `{"links": [...], "known_targets": {...}}` when the caller
+/// wants broken-link detection against a known set of heading slugs and
+/// document ids. Keeping this as one argument (rather than a second NIF
+/// parameter) means existing `validate_links/1` callers keep working
+/// unchanged.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Input {
+    Links(Vec<LinkRecord>),
+    WithKnownTargets {
+        links: Vec<LinkRecord>,
+        #[serde(default)]
+        known_targets: KnownTargets,
+    },
+}
+
+pub fn validate(input_json: &str) -> Result<Value, serde_json::Error> {
+    let (links, known) = match serde_json::from_str(input_json)? {
+        Input::Links(links) => (links, KnownTargets::default()),
+        Input::WithKnownTargets {
+            links,
+            known_targets,
+        } => (links, known_targets),
+    };
+
+    let diagnostics = links.iter().map(|link| diagnose(link, &known)).collect();
+    Ok(Value::Array(diagnostics))
+}
+
+fn diagnose(link: &LinkRecord, known: &KnownTargets) -> Value {
+    let category = classify(link);
+    let (severity, reason) = check(link, category, known);
+
+    json!({
+        "text": link.text.clone().unwrap_or_default(),
+        "url": link.url,
+        "category": category.as_str(),
+        "severity": severity,
+        "reason": reason,
+    })
+}
+
+fn classify(link: &LinkRecord) -> Category {
+    if link.kind.as_deref() == Some("wikilink") {
+        return Category::Wikilink;
+    }
+    if link.url.starts_with('#') {
+        return Category::Anchor;
+    }
+    if is_external(&link.url) {
+        Category::External
+    } else {
+        Category::Relative
+    }
+}
+
+fn is_external(url: &str) -> bool {
+    match url.find(':') {
+        Some(colon) => {
+            let scheme = &url[..colon];
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        None => false,
+    }
+}
+
+fn check(link: &LinkRecord, category: Category, known: &KnownTargets) -> (&'static str, &'static str) {
+    let target = link.url.trim();
+
+    if target.is_empty() && link.fragment.as_deref().unwrap_or("").is_empty() {
+        return ("error", "empty_target");
+    }
+
+    match category {
+        Category::External => {
+            if !is_well_formed_external(target) {
+                ("error", "malformed_url")
+            } else {
+                ("ok", "ok")
+            }
+        }
+        Category::Relative => ("ok", "ok"),
+        Category::Anchor => {
+            let slug = target.trim_start_matches('#');
+            if known.heading_slugs.is_empty() || known.heading_slugs.contains(slug) {
+                ("ok", "ok")
+            } else {
+                ("warning", "unresolved_anchor")
+            }
+        }
+        Category::Wikilink => {
+            let page = link.target.as_deref().unwrap_or(target);
+            let page_known = known.document_ids.is_empty() || known.document_ids.contains(page);
+            let fragment_known = match &link.fragment {
+                Some(fragment) if !fragment.is_empty() => {
+                    known.heading_slugs.is_empty() || known.heading_slugs.contains(fragment.as_str())
+                }
+                _ => true,
+            };
+            if page_known && fragment_known {
+                ("ok", "ok")
+            } else {
+                ("warning", "unresolved_wikilink")
+            }
+        }
+    }
+}
+
+fn is_well_formed_external(url: &str) -> bool {
+    match url.find("://") {
+        Some(idx) => idx > 0 && !url[idx + 3..].is_empty(),
+        None => url.starts_with("mailto:") || url.starts_with("tel:"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_external_relative_and_anchor_links() {
+        let input = r##"[
+            {"url": "https://example.com"},
+            {"url": "./docs/page.md"},
+            {"url": "#section"}
+        ]"##;
+
+        let diagnostics = validate(input).unwrap();
+        assert_eq!(diagnostics[0]["category"], "external");
+        assert_eq!(diagnostics[0]["severity"], "ok");
+        assert_eq!(diagnostics[1]["category"], "relative");
+        assert_eq!(diagnostics[2]["category"], "anchor");
+    }
+
+    #[test]
+    fn flags_malformed_external_urls() {
+        let input = r#"[{"url": "https://"}]"#;
+        let diagnostics = validate(input).unwrap();
+        assert_eq!(diagnostics[0]["severity"], "error");
+        assert_eq!(diagnostics[0]["reason"], "malformed_url");
+    }
+
+    #[test]
+    fn flags_empty_targets() {
+        let input = r#"[{"url": ""}]"#;
+        let diagnostics = validate(input).unwrap();
+        assert_eq!(diagnostics[0]["severity"], "error");
+        assert_eq!(diagnostics[0]["reason"], "empty_target");
+    }
+
+    #[test]
+    fn anchors_resolve_against_known_heading_slugs() {
+        let input = r##"{
+            "links": [{"url": "#intro"}, {"url": "#missing"}],
+            "known_targets": {"heading_slugs": ["intro"]}
+        }"##;
+
+        let diagnostics = validate(input).unwrap();
+        assert_eq!(diagnostics[0]["severity"], "ok");
+        assert_eq!(diagnostics[1]["severity"], "warning");
+        assert_eq!(diagnostics[1]["reason"], "unresolved_anchor");
+    }
+
+    #[test]
+    fn wikilinks_resolve_against_known_document_ids() {
+        let input = r#"{
+            "links": [
+                {"kind": "wikilink", "url": "Known Page", "target": "Known Page"},
+                {"kind": "wikilink", "url": "Missing Page", "target": "Missing Page"}
+            ],
+            "known_targets": {"document_ids": ["Known Page"]}
+        }"#;
+
+        let diagnostics = validate(input).unwrap();
+        assert_eq!(diagnostics[0]["category"], "wikilink");
+        assert_eq!(diagnostics[0]["severity"], "ok");
+        assert_eq!(diagnostics[1]["severity"], "warning");
+        assert_eq!(diagnostics[1]["reason"], "unresolved_wikilink");
+    }
+}
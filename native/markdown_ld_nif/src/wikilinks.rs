@@ -0,0 +1,83 @@
+use serde_json::{json, Value};
+
+/// Scans a run of plain text for Obsidian/Roam-style `[[Target]]` and
+/// `[[Target|Alias]]` wiki-links (optionally with a `#heading` fragment, e.g.
+/// `[[Target#Section|Alias]]`), returning one `links` entry per match with
+/// `kind: "wikilink"`.
+pub fn scan(text: &str) -> Vec<Value> {
+    let mut found = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'[' && bytes.get(i + 1) == Some(&b'[') {
+            if let Some(close) = text[i + 2..].find("]]") {
+                let inner = &text[i + 2..i + 2 + close];
+                if !inner.is_empty() && !inner.contains(['[', ']']) {
+                    found.push(parse_target(inner));
+                }
+                i += 2 + close + 2;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    found
+}
+
+fn parse_target(inner: &str) -> Value {
+    let (target, alias) = match inner.split_once('|') {
+        Some((target, alias)) => (target, Some(alias)),
+        None => (inner, None),
+    };
+    let (page, fragment) = match target.split_once('#') {
+        Some((page, fragment)) => (page, Some(fragment)),
+        None => (target, None),
+    };
+
+    json!({
+        "kind": "wikilink",
+        "text": alias.unwrap_or(target).trim().to_string(),
+        "url": target.trim().to_string(),
+        "title": Value::Null,
+        "target": page.trim().to_string(),
+        "fragment": fragment.map(|f| f.trim().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_a_plain_target() {
+        let found = scan("see [[Other Page]] for more");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["target"], "Other Page");
+        assert_eq!(found[0]["text"], "Other Page");
+        assert_eq!(found[0]["fragment"], Value::Null);
+    }
+
+    #[test]
+    fn finds_target_with_alias_and_fragment() {
+        let found = scan("[[Other Page#Section|alias]]");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["target"], "Other Page");
+        assert_eq!(found[0]["fragment"], "Section");
+        assert_eq!(found[0]["text"], "alias");
+    }
+
+    #[test]
+    fn ignores_empty_and_unclosed_brackets() {
+        assert!(scan("[[]]").is_empty());
+        assert!(scan("[[unclosed").is_empty());
+    }
+
+    #[test]
+    fn finds_multiple_targets_in_one_run() {
+        let found = scan("[[A]] and [[B]]");
+        let targets: Vec<&str> = found.iter().map(|v| v["target"].as_str().unwrap()).collect();
+        assert_eq!(targets, vec!["A", "B"]);
+    }
+}